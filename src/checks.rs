@@ -0,0 +1,183 @@
+use std::{collections::HashMap, sync::Arc};
+
+use serenity::{
+    client::Context,
+    framework::standard::{
+        macros::check,
+        Args, CommandOptions, Reason,
+    },
+    model::{channel::Message, guild::Member, id::MessageId},
+    prelude::{RwLock, TypeMapKey},
+};
+
+use crate::{strings::Strings, types::Committee, types::Config, ConfigContainer, StringsContainer};
+
+/// The member and committee the [`Delegate`](DELEGATE_CHECK) check resolved
+/// for a given message, stashed so the command body it guards starts with
+/// both already resolved instead of redoing the lookups itself.
+/// `committee_channel_id` is `None` when the member holds the delegate role
+/// but isn't seated on any configured committee.
+pub struct ResolvedCaller {
+    pub member: Member,
+    pub committee_channel_id: Option<u64>,
+}
+
+/// Caches the [`ResolvedCaller`] the [`Delegate`](DELEGATE_CHECK) check
+/// resolved for a given message, so the command body it guards doesn't have
+/// to re-fetch the member or re-resolve their committee. Entries are popped
+/// (not just read) by the command that consumes them, since a message id is
+/// never dispatched through the same check twice.
+pub struct ResolvedCallerContainer;
+
+impl TypeMapKey for ResolvedCallerContainer {
+    type Value = Arc<RwLock<HashMap<MessageId, ResolvedCaller>>>;
+}
+
+/// Takes the caller the `Delegate` check resolved for `msg`, if any. Falls
+/// back to re-fetching the member and re-resolving their committee when the
+/// cache was never populated (e.g. the check was bypassed), so callers can
+/// treat this as a drop-in replacement for a direct `get_member` call plus
+/// [`resolve_committee`].
+pub async fn take_resolved_caller(
+    ctx: &Context,
+    msg: &Message,
+    config: &Config,
+) -> Option<ResolvedCaller> {
+    let cache = {
+        let data = ctx.data.read().await;
+        data.get::<ResolvedCallerContainer>().unwrap().clone()
+    };
+
+    if let Some(caller) = cache.write().await.remove(&msg.id) {
+        return Some(caller);
+    }
+
+    let member = ctx
+        .http
+        .get_member(config.guild_id(), msg.author.id.into())
+        .await
+        .ok()?;
+    let committee_channel_id = resolve_committee(config, &member).map(Committee::channel_id);
+
+    Some(ResolvedCaller {
+        member,
+        committee_channel_id,
+    })
+}
+
+/// Fetches the shared config snapshot and strings table, the first thing
+/// nearly every command and check needs from the `TypeMap`.
+pub async fn shared_data(ctx: &Context) -> (Config, Arc<Strings>) {
+    let (config, strings) = config_lock_and_strings(ctx).await;
+
+    (config.read().await.clone(), strings)
+}
+
+/// Like [`shared_data`], but returns the live `Config` lock rather than a
+/// snapshot, for commands that need to persist an edit.
+pub async fn config_lock_and_strings(ctx: &Context) -> (Arc<serenity::prelude::RwLock<Config>>, Arc<Strings>) {
+    let data = ctx.data.read().await;
+
+    (
+        data.get::<ConfigContainer>().unwrap().clone(),
+        data.get::<StringsContainer>().unwrap().clone(),
+    )
+}
+
+/// Finds the committee whose role `member` holds, if any.
+pub fn resolve_committee<'a>(config: &'a Config, member: &Member) -> Option<&'a Committee> {
+    config
+        .committees()
+        .iter()
+        .find(|committee| member.roles.contains(&committee.role_id().into()))
+}
+
+/// Gates a command to members holding the configured delegate role. On
+/// failure, carries the strings-table key to reply with back through
+/// [`Reason::User`] for the framework's dispatch error hook to render.
+#[check]
+#[name = "Delegate"]
+async fn delegate_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    let (config, _) = shared_data(ctx).await;
+
+    let member = ctx
+        .http
+        .get_member(config.guild_id(), msg.author.id.into())
+        .await
+        .map_err(|_| Reason::User("unknown_member".to_owned()))?;
+
+    if !member.roles.contains(&config.delegate_role_id().into()) {
+        return Err(Reason::User("delegate_only".to_owned()));
+    }
+
+    let committee_channel_id = resolve_committee(&config, &member).map(Committee::channel_id);
+
+    let cache = {
+        let data = ctx.data.read().await;
+        data.get::<ResolvedCallerContainer>().unwrap().clone()
+    };
+    cache.write().await.insert(
+        msg.id,
+        ResolvedCaller {
+            member,
+            committee_channel_id,
+        },
+    );
+
+    Ok(())
+}
+
+/// Gates a command to messages sent in the configured guild. Shared by any
+/// command (e.g. `join`) that needs to reject DMs and foreign guilds before
+/// doing anything else.
+#[check]
+#[name = "InGuild"]
+async fn in_guild_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    let (config, _) = shared_data(ctx).await;
+
+    let in_guild = msg
+        .guild_id
+        .map_or(false, |id| id.as_u64() == &config.guild_id());
+
+    if !in_guild {
+        return Err(Reason::User("join_wrong_guild".to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Gates a command to members holding the staff or chair role.
+#[check]
+#[name = "StaffOrChair"]
+async fn staff_or_chair_check(
+    ctx: &Context,
+    msg: &Message,
+    _: &mut Args,
+    _: &CommandOptions,
+) -> Result<(), Reason> {
+    let (config, _) = shared_data(ctx).await;
+
+    let member = msg
+        .member(ctx)
+        .await
+        .map_err(|_| Reason::User("unknown_member".to_owned()))?;
+
+    let is_admin = member.roles.contains(&config.staff_role_id().into())
+        || member.roles.contains(&config.chair_role_id().into());
+
+    if !is_admin {
+        return Err(Reason::User("settings_unauthorized".to_owned()));
+    }
+
+    Ok(())
+}
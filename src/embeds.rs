@@ -0,0 +1,118 @@
+use serenity::{builder::CreateEmbed, model::{timestamp::Timestamp, user::User}, utils::Colour};
+
+use crate::strings::Strings;
+
+/// Discord's per-field character budget; message bodies longer than this are
+/// split across additional fields rather than truncated.
+const FIELD_CHUNK_LEN: usize = 1024;
+
+/// Conservative ceiling on how much of the message body this embed will
+/// render. Discord caps a whole embed (title, fields, footer, etc. combined)
+/// at 6000 characters and 25 fields; this relay only ever adds a couple of
+/// small fixed fields (delegate, recipient) alongside the message chunks, so
+/// budgeting 4096 characters for the body leaves ample headroom without
+/// having to track the other fields' exact sizes. Content beyond this is cut
+/// off with an explicit notice rather than risking Discord rejecting the
+/// whole embed outright.
+const MAX_MESSAGE_CHARS: usize = 4096;
+
+/// Where a relay request currently stands. Drives the embed's accent color
+/// so staff can tell a request's state at a glance in the committee channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelayStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl RelayStatus {
+    fn color(self) -> Colour {
+        match self {
+            RelayStatus::Pending => Colour::LIGHT_GREY,
+            RelayStatus::Approved => Colour::DARK_GREEN,
+            RelayStatus::Rejected => Colour::RED,
+        }
+    }
+}
+
+/// Splits `content` into chunks of at most `max_len` characters, on char
+/// boundaries, so long delegate messages are never silently truncated by
+/// Discord's embed field limit. Returns no chunks for empty content — Discord
+/// rejects embed fields with an empty value, so callers must handle that case
+/// themselves rather than being handed a single empty chunk.
+pub fn chunk_content(content: &str, max_len: usize) -> Vec<String> {
+    if content.is_empty() {
+        return Vec::new();
+    }
+
+    let chars: Vec<char> = content.chars().collect();
+
+    chars
+        .chunks(max_len)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Builds the relay request embed shown in the committee channel, the
+/// delegate's confirmation, and the recipient's DM alike: who sent it, who
+/// it's bound for (if anyone), the message body, and a color keyed to
+/// `status`.
+pub fn relay_embed<'a>(
+    e: &'a mut CreateEmbed,
+    strings: &Strings,
+    lang: &str,
+    author: &User,
+    recipient: Option<&User>,
+    content: &str,
+    status: RelayStatus,
+    timestamp: Timestamp,
+) -> &'a mut CreateEmbed {
+    e.color(status.color())
+        .timestamp(timestamp)
+        .field(strings.get(lang, "embed_delegate_field"), author.mention(), true);
+
+    if let Some(recipient) = recipient {
+        e.field(
+            strings.get(lang, "embed_recipient_field"),
+            recipient.mention(),
+            true,
+        );
+    }
+
+    let message_field = strings.get(lang, "embed_message_field");
+    let continued_suffix = strings.get(lang, "embed_message_field_continued");
+
+    let char_count = content.chars().count();
+    let (truncated_content, omitted) = if char_count > MAX_MESSAGE_CHARS {
+        let kept: String = content.chars().take(MAX_MESSAGE_CHARS).collect();
+        (kept, char_count - MAX_MESSAGE_CHARS)
+    } else {
+        (content.to_owned(), 0)
+    };
+
+    let chunks = chunk_content(&truncated_content, FIELD_CHUNK_LEN);
+
+    if chunks.is_empty() {
+        e.field(message_field, strings.get(lang, "embed_message_empty"), false);
+    } else {
+        for (i, chunk) in chunks.iter().enumerate() {
+            let name = if i == 0 {
+                message_field.to_owned()
+            } else {
+                format!("{} {}", message_field, continued_suffix)
+            };
+
+            e.field(name, chunk, false);
+        }
+    }
+
+    if omitted > 0 {
+        e.field(
+            strings.get(lang, "embed_message_truncated_field"),
+            strings.get_fmt(lang, "embed_message_truncated", &[("count", &omitted.to_string())]),
+            false,
+        );
+    }
+
+    e
+}
@@ -1,36 +1,73 @@
-use std::{collections::HashSet, fs::File, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::{Context as _, Result};
 use serenity::{
     async_trait,
+    builder::CreateComponents,
     framework::standard::{
-        macros::{command, group},
-        Args, CommandResult, StandardFramework,
+        macros::{checks, command, group},
+        Args, CommandResult, DispatchError, Reason, StandardFramework,
     },
-    futures::StreamExt,
     http::Http,
     model::{
-        channel::Message,
+        channel::{GuildChannel, Message},
         gateway::Ready,
-        id::{RoleId, UserId},
+        id::{ChannelId, RoleId, UserId},
+        interactions::{message_component::ButtonStyle, InteractionResponseType},
+        webhook::Webhook,
     },
     prelude::*,
     utils::{content_safe, ContentSafeOptions, MessageBuilder},
 };
-use tracing::info;
+use tokio::time::Instant;
+use tracing::{info, warn};
 use types::*;
 
+mod checks;
+mod embeds;
+mod store;
+mod strings;
 mod types;
 
+use checks::{
+    config_lock_and_strings, shared_data, take_resolved_caller, ResolvedCallerContainer,
+    DELEGATE_CHECK, IN_GUILD_CHECK, STAFF_OR_CHAIR_CHECK,
+};
+use embeds::{relay_embed, RelayStatus};
+use store::{NewRelay, Store};
+use strings::Strings;
+
 const POSITIVE_REACTION: char = '✅';
-const NEGATIVE_REACTION: char = '❌';
 const SENT_REACTION: char = '📨';
 const REACTION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 
+const APPROVE_CUSTOM_ID: &str = "mun_approve";
+const DENY_CUSTOM_ID: &str = "mun_deny";
+
+const RELAY_DB_PATH: &str = "relay.db";
+const STRINGS_PATH: &str = "strings.ron";
+const CONFIG_PATH: &str = "config.ron";
+
 struct ConfigContainer;
 
 impl TypeMapKey for ConfigContainer {
-    type Value = Arc<Config>;
+    type Value = Arc<RwLock<Config>>;
+}
+
+struct StoreContainer;
+
+impl TypeMapKey for StoreContainer {
+    type Value = Arc<Store>;
+}
+
+struct StringsContainer;
+
+impl TypeMapKey for StringsContainer {
+    type Value = Arc<Strings>;
 }
 
 struct Handler;
@@ -40,53 +77,158 @@ impl EventHandler for Handler {
     async fn ready(&self, _: Context, ready: Ready) {
         info!("{} is connected!", ready.user.name);
     }
+
+    async fn message(&self, ctx: Context, msg: Message) {
+        if msg.author.bot {
+            return;
+        }
+
+        let referenced_id = match msg.message_reference.as_ref().and_then(|r| r.message_id) {
+            Some(id) => id,
+            None => return,
+        };
+
+        let (config, strings) = shared_data(&ctx).await;
+        let store = {
+            let data = ctx.data.read().await;
+            data.get::<StoreContainer>().unwrap().clone()
+        };
+
+        let relay = match store.find_by_committee_msg(referenced_id.into()).await {
+            Ok(Some(relay)) => relay,
+            Ok(None) => return,
+            Err(why) => {
+                warn!("failed to look up relay for reply: {}", why);
+                return;
+            }
+        };
+
+        let cleaned_content =
+            content_safe(&ctx.cache, &msg.content, &ContentSafeOptions::default()).await;
+
+        let delivery = ChannelId(relay.delegate_channel_id)
+            .say(
+                &ctx,
+                &MessageBuilder::new()
+                    .push(strings.get(config.lang(), "relay_reply_prefix"))
+                    .push(" ")
+                    .mention(&msg.author)
+                    .push_line(":")
+                    .push_quote_line(cleaned_content.clone()),
+            )
+            .await;
+
+        if let Err(why) = delivery {
+            warn!("failed to deliver relay reply: {}", why);
+            return;
+        }
+
+        if let Err(why) = store
+            .record_message(relay.id, msg.author.id.into(), &cleaned_content)
+            .await
+        {
+            warn!("failed to record relay message: {}", why);
+        }
+
+        if let Err(why) = msg.react(&ctx, SENT_REACTION).await {
+            warn!("failed to react to relay reply: {}", why);
+        }
+    }
 }
 
-#[group("relay")]
-#[commands(forward)]
-struct Relay;
+/// Builds the approve/deny action row shared by the webhook and bot-authored
+/// committee message paths.
+fn approval_components(c: &mut CreateComponents) -> &mut CreateComponents {
+    c.create_action_row(|row| {
+        row.create_button(|b| {
+            b.custom_id(APPROVE_CUSTOM_ID)
+                .label("Approve")
+                .style(ButtonStyle::Success)
+        })
+        .create_button(|b| {
+            b.custom_id(DENY_CUSTOM_ID)
+                .label("Deny")
+                .style(ButtonStyle::Danger)
+        })
+    })
+}
 
-#[command("forward")]
-async fn forward(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
-    let config = {
-        let data = ctx.data.read().await;
-        data.get::<ConfigContainer>().unwrap().clone()
-    };
+/// Finds this committee's cached relay webhook, or registers a new one and
+/// caches its id/token in `config.ron` so future calls don't need to list
+/// (and guess among) the channel's webhooks at all.
+async fn get_or_create_webhook(
+    ctx: &Context,
+    config_lock: &Arc<RwLock<Config>>,
+    channel: &GuildChannel,
+    committee: &Committee,
+) -> Result<Webhook> {
+    if let Some((id, token)) = committee.webhook_credentials() {
+        if let Ok(webhook) = ctx.http.get_webhook_with_token(id, token).await {
+            return Ok(webhook);
+        }
+
+        warn!(
+            "cached webhook for committee {:?} no longer resolves; recreating it",
+            committee.name()
+        );
+    }
 
-    let delegate_member = if let Ok(member) = ctx
-        .http
-        .get_member(config.guild_id(), msg.author.id.into())
+    // Serenity's `GuildChannel::create_webhook` only takes a name; there's no
+    // creation-time avatar parameter (and Discord's endpoint wants base64
+    // image data there, not a URL anyway). `committee.webhook_avatar()` is
+    // instead applied per-message as a fallback, alongside the delegate's own
+    // avatar, wherever the webhook is executed.
+    let webhook = channel
+        .create_webhook(ctx, committee.webhook_name().unwrap_or("Mun Relay"))
         .await
-    {
-        member
-    } else {
-        msg.channel_id
-            .say(ctx, "Umm... have I made your acquaintance?")
-            .await?;
+        .context("failed to create committee webhook")?;
 
-        return Ok(());
-    };
+    let token = webhook
+        .token
+        .clone()
+        .context("expected a freshly created webhook to carry a token")?;
 
-    if !delegate_member
-        .roles
-        .contains(&config.delegate_role_id().into())
     {
-        msg.channel_id
-            .say(ctx, format!("This command is only available to delegates."))
-            .await?;
+        let mut config = config_lock.write().await;
 
-        return Ok(());
+        if let Some(committee) = config.committee_mut(committee.channel_id()) {
+            committee.set_webhook_credentials(webhook.id.into(), token);
+        }
+
+        if let Err(why) = config.save(CONFIG_PATH) {
+            warn!("failed to persist committee webhook credentials: {}", why);
+        }
     }
 
-    let committee = if let Some(committee) = config
-        .committees()
-        .iter()
-        .find(|&committee| delegate_member.roles.contains(&committee.role_id().into()))
+    Ok(webhook)
+}
+
+#[group("relay")]
+#[commands(forward, history)]
+struct Relay;
+
+#[command("forward")]
+#[checks(Delegate)]
+async fn forward(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (config_lock, strings) = config_lock_and_strings(ctx).await;
+    let config = config_lock.read().await.clone();
+    let lang = config.lang();
+
+    // The Delegate check already fetched this member and resolved their
+    // committee; reuse both instead of redoing that work here.
+    let resolved = take_resolved_caller(ctx, msg, &config)
+        .await
+        .context("delegate member vanished after passing the Delegate check")?;
+    let delegate_member = resolved.member;
+
+    let committee = if let Some(committee) = resolved
+        .committee_channel_id
+        .and_then(|channel_id| config.committee(channel_id))
     {
         committee
     } else {
         msg.channel_id
-            .say(ctx, "Sorry, but I'm not sure which committee you're on.")
+            .say(ctx, strings.get(lang, "unknown_committee"))
             .await?;
 
         return Ok(());
@@ -100,125 +242,279 @@ async fn forward(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 
     let recipient_id = args.single::<UserId>().ok();
     let is_external = recipient_id.is_some();
+    let recipient_user = match recipient_id {
+        Some(recipient_id) => Some(recipient_id.to_user(ctx).await?),
+        None => None,
+    };
 
     let cleaned_content = content_safe(ctx, args.rest(), &ContentSafeOptions::default()).await;
 
     let typing = msg.channel_id.start_typing(&ctx.http)?;
 
-    let committee_msg = committee_channel
-        .say(
-            ctx,
-            &MessageBuilder::new()
-                .push("Received request from ")
-                .mention(&msg.author)
-                .push(if is_external {
-                    format!(
-                        " to forward message to {}",
-                        &recipient_id.unwrap().mention()
-                    )
-                } else {
-                    String::new()
-                })
-                .push_line(":")
-                .push_quote_line(cleaned_content.clone())
-                .push_line("")
-                .push(if is_external {
-                    "Use the reactions below to approve or deny this request."
-                } else {
-                    ""
-                })
-                .push(format!(
-                    "Reply to this message{}to send a response.",
-                    if is_external { " after voting " } else { " " }
-                ))
-                .build(),
+    let hint = if is_external {
+        format!(
+            "{} {}",
+            strings.get(lang, "forward_buttons_hint"),
+            strings.get(lang, "forward_reply_hint_voting"),
         )
-        .await?;
-
-    if is_external {
-        committee_msg.react(ctx, POSITIVE_REACTION).await?;
-        committee_msg.react(ctx, NEGATIVE_REACTION).await?;
-    }
-
-    msg.reply(
-        ctx,
-        &MessageBuilder::new()
-            .push("Your message has been forwarded to ")
-            .push_bold_safe(committee.name())
-            .push(if is_external { " for approval" } else { "" })
-            .push(".")
-            .build(),
-    )
-    .await?;
+    } else {
+        strings.get(lang, "forward_reply_hint").to_owned()
+    };
 
-    typing.stop();
+    let initial_status = if is_external {
+        RelayStatus::Pending
+    } else {
+        RelayStatus::Approved
+    };
 
-    if is_external {
-        let approved = if let Some(reaction) = committee_msg
-            .await_reaction(ctx)
-            .timeout(REACTION_TIMEOUT)
+    let webhook = if config.webhooks_enabled() {
+        get_or_create_webhook(ctx, &config_lock, &committee_channel, committee)
             .await
-        {
-            match reaction
-                .as_inner_ref()
-                .emoji
-                .as_data()
-                .chars()
-                .next()
-                .unwrap()
-            {
-                POSITIVE_REACTION => {
-                    committee_msg
-                        .reply(
-                            ctx,
-                            &MessageBuilder::new()
-                                .push("This request has been ")
-                                .push_bold("approved")
-                                .push(".")
-                                .build(),
+            .ok()
+    } else {
+        None
+    };
+
+    let committee_msg = if let Some(webhook) = webhook {
+        webhook
+            .execute(ctx, true, |w| {
+                w.username(delegate_member.display_name().into_owned())
+                    .embed(|e| {
+                        relay_embed(
+                            e,
+                            &strings,
+                            lang,
+                            &msg.author,
+                            recipient_user.as_ref(),
+                            &cleaned_content,
+                            initial_status,
+                            msg.timestamp,
                         )
-                        .await?;
+                        .footer(|f| f.text(&hint))
+                    });
 
-                    true
+                if let Some(avatar_url) = msg.author.avatar_url().or_else(|| {
+                    committee.webhook_avatar().map(str::to_owned)
+                }) {
+                    w.avatar_url(avatar_url);
                 }
-                NEGATIVE_REACTION => {
-                    committee_msg
-                        .reply(
-                            ctx,
-                            &MessageBuilder::new()
-                                .push("This request has been ")
-                                .push_bold("rejected")
-                                .push(".")
-                                .build(),
-                        )
-                        .await?;
 
-                    false
+                if is_external {
+                    w.components(approval_components);
                 }
-                _ => {
-                    committee_msg
-                        .reply(ctx, "Invalid reaction; rejecting request.")
-                        .await?;
 
-                    false
+                w
+            })
+            .await?
+            .context("expected committee webhook to return its message")?
+    } else {
+        committee_channel
+            .send_message(ctx, |m| {
+                m.content(
+                    &MessageBuilder::new()
+                        .push(strings.get(lang, "request_received_prefix"))
+                        .push(" ")
+                        .mention(&msg.author)
+                        .push(".")
+                        .build(),
+                )
+                .embed(|e| {
+                    relay_embed(
+                        e,
+                        &strings,
+                        lang,
+                        &msg.author,
+                        recipient_user.as_ref(),
+                        &cleaned_content,
+                        initial_status,
+                        msg.timestamp,
+                    )
+                    .footer(|f| f.text(&hint))
+                });
+
+                if is_external {
+                    m.components(approval_components);
                 }
+
+                m
+            })
+            .await?
+    };
+
+    let store = {
+        let data = ctx.data.read().await;
+        data.get::<StoreContainer>().unwrap().clone()
+    };
+
+    // Opened as soon as the request exists, not once a vote resolves, so
+    // `history` can show a rejected request's conversation too. `approved`
+    // stays `None` here for internal requests (which never vote) and is
+    // updated to the verdict below once an external request's vote concludes.
+    let relay_id = store
+        .insert_relay(&NewRelay {
+            committee_msg_id: committee_msg.id.into(),
+            delegate_channel_id: msg.channel_id.into(),
+            delegate_user_id: msg.author.id.into(),
+            recipient_id: recipient_id.map(Into::into),
+            committee_id: committee.channel_id(),
+            approved: None,
+        })
+        .await?;
+
+    store
+        .record_message(relay_id, msg.author.id.into(), &cleaned_content)
+        .await?;
+
+    let confirmation_title_key = if is_external {
+        "forwarded_confirmation_title_external"
+    } else {
+        "forwarded_confirmation_title"
+    };
+    let confirmation_title =
+        strings.get_fmt(lang, confirmation_title_key, &[("committee", committee.name())]);
+
+    msg.channel_id
+        .send_message(ctx, |m| {
+            m.reference_message(msg).embed(|e| {
+                relay_embed(
+                    e,
+                    &strings,
+                    lang,
+                    &msg.author,
+                    recipient_user.as_ref(),
+                    &cleaned_content,
+                    initial_status,
+                    msg.timestamp,
+                )
+                .title(confirmation_title)
+            })
+        })
+        .await?;
+
+    typing.stop();
+
+    if is_external {
+        let vote_deadline = Instant::now() + REACTION_TIMEOUT;
+        let mut verdict = None;
+
+        loop {
+            let remaining = vote_deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                break;
             }
-        } else {
+
+            let interaction = match committee_msg
+                .await_component_interaction(ctx)
+                .timeout(remaining)
+                .await
+            {
+                Some(interaction) => interaction,
+                None => break,
+            };
+
+            let voter_member = ctx
+                .http
+                .get_member(config.guild_id(), interaction.user.id.into())
+                .await;
+
+            let is_voter = matches!(voter_member, Ok(ref member)
+                if member.roles.contains(&config.staff_role_id().into())
+                    || member.roles.contains(&config.chair_role_id().into()));
+
+            if !is_voter {
+                interaction
+                    .create_interaction_response(ctx, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.ephemeral(true)
+                                    .content(strings.get(lang, "vote_unauthorized"))
+                            })
+                    })
+                    .await?;
+
+                continue;
+            }
+
+            interaction
+                .create_interaction_response(ctx, |r| {
+                    r.kind(InteractionResponseType::DeferredUpdateMessage)
+                })
+                .await?;
+
+            verdict = Some((interaction.data.custom_id == APPROVE_CUSTOM_ID, interaction.user));
+
+            break;
+        }
+
+        let final_status = match verdict {
+            Some((true, _)) => RelayStatus::Approved,
+            Some((false, _)) | None => RelayStatus::Rejected,
+        };
+
+        committee_msg
+            .edit(ctx, |m| {
+                m.components(|c| c).embed(|e| {
+                    relay_embed(
+                        e,
+                        &strings,
+                        lang,
+                        &msg.author,
+                        recipient_user.as_ref(),
+                        &cleaned_content,
+                        final_status,
+                        msg.timestamp,
+                    )
+                })
+            })
+            .await?;
+
+        let (approved, approved_by) = if let Some((approved, voter)) = verdict {
+            let verdict_key = if approved {
+                "verdict_approved"
+            } else {
+                "verdict_rejected"
+            };
+
             committee_msg
                 .reply(
                     ctx,
-                    "No consensus reached in 10 minutes; rejecting request.",
+                    &MessageBuilder::new()
+                        .push(strings.get(lang, "vote_result_prefix"))
+                        .push(" ")
+                        .push_bold(strings.get(lang, verdict_key))
+                        .push(strings.get(lang, "vote_result_by_suffix"))
+                        .push(" ")
+                        .mention(&voter)
+                        .push(".")
+                        .build(),
                 )
                 .await?;
 
-            false
+            (approved, Some(voter.id.into()))
+        } else {
+            committee_msg
+                .reply(ctx, strings.get(lang, "vote_timeout"))
+                .await?;
+
+            (false, None)
         };
 
+        store.set_approved(relay_id, approved, approved_by).await?;
+
         msg.reply(
             ctx,
             &MessageBuilder::new()
-                .push("This request has been ")
-                .push_bold(if approved { "approved" } else { "rejected" })
+                .push(strings.get(lang, "request_result_prefix"))
+                .push(" ")
+                .push_bold(strings.get(
+                    lang,
+                    if approved {
+                        "verdict_approved"
+                    } else {
+                        "verdict_rejected"
+                    },
+                ))
                 .push(".")
                 .build(),
         )
@@ -229,55 +525,69 @@ async fn forward(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
                 .unwrap()
                 .create_dm_channel(ctx)
                 .await?
-                .say(
-                    ctx,
-                    &MessageBuilder::new()
-                        .push("Received message from ")
-                        .mention(&msg.author)
-                        .push_line(":")
-                        .push_quote_line(cleaned_content.clone()),
-                )
+                .send_message(ctx, |m| {
+                    m.embed(|e| {
+                        relay_embed(
+                            e,
+                            &strings,
+                            lang,
+                            &msg.author,
+                            None,
+                            &cleaned_content,
+                            RelayStatus::Approved,
+                            msg.timestamp,
+                        )
+                        .title(strings.get(lang, "recipient_dm_prefix"))
+                    })
+                })
                 .await?;
         }
     }
 
-    let committee_msg_id = committee_msg.id;
-
-    let mut replies = committee_channel
-        .id
-        .await_replies(ctx)
-        .timeout(REACTION_TIMEOUT)
-        .filter(move |msg| match msg.message_reference {
-            Some(ref msg_ref) => match msg_ref.message_id {
-                Some(m) => m == committee_msg_id,
-                None => false,
-            },
-            None => false,
-        })
-        .await;
+    Ok(())
+}
 
-    while let Some(reply_msg) = replies.next().await {
-        let cleaned_content = content_safe(
-            &ctx.cache,
-            &reply_msg.content,
-            &ContentSafeOptions::default(),
-        )
-        .await;
+#[command("history")]
+async fn history(ctx: &Context, msg: &Message) -> CommandResult {
+    let (config, strings) = shared_data(ctx).await;
+    let store = {
+        let data = ctx.data.read().await;
+        data.get::<StoreContainer>().unwrap().clone()
+    };
+    let lang = config.lang();
 
-        msg.channel_id
-            .say(
-                ctx,
-                &MessageBuilder::new()
-                    .push("Received reply from ")
-                    .mention(&reply_msg.author)
-                    .push_line(":")
-                    .push_quote_line(cleaned_content.clone()),
-            )
-            .await?;
+    let relay = match store
+        .latest_for_delegate_channel(msg.channel_id.into())
+        .await?
+    {
+        Some(relay) => relay,
+        None => {
+            msg.reply(ctx, strings.get(lang, "history_none")).await?;
+
+            return Ok(());
+        }
+    };
+
+    let entries = store.history(relay.id).await?;
 
-        reply_msg.react(ctx, SENT_REACTION).await?;
+    if entries.is_empty() {
+        msg.reply(ctx, strings.get(lang, "history_empty")).await?;
+
+        return Ok(());
+    }
+
+    let mut builder = MessageBuilder::new();
+
+    for entry in entries {
+        builder
+            .mention(&UserId(entry.author_id))
+            .push_line(":")
+            .push_quote_line(entry.content)
+            .push_line("");
     }
 
+    msg.channel_id.say(ctx, builder.build()).await?;
+
     Ok(())
 }
 
@@ -286,24 +596,10 @@ async fn forward(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult
 struct Role;
 
 #[command("join")]
+#[checks(InGuild)]
 async fn join(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
-    let config = {
-        let data = ctx.data.read().await;
-        data.get::<ConfigContainer>().unwrap().clone()
-    };
-
-    let in_valid_guild = match msg.guild_id {
-        Some(id) => id.as_u64() == &config.guild_id(),
-        None => false,
-    };
-
-    if !in_valid_guild {
-        msg.channel_id
-            .say(ctx, "I'm not configured to work here.")
-            .await?;
-
-        return Ok(());
-    }
+    let (config, strings) = shared_data(ctx).await;
+    let lang = config.lang();
 
     let guild = msg.guild(ctx).await.unwrap();
 
@@ -315,7 +611,7 @@ async fn join(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     }) {
         committee
     } else {
-        msg.reply(ctx, "Sorry, I couldn't find a committee by that name.")
+        msg.reply(ctx, strings.get(lang, "join_unknown_committee"))
             .await?;
 
         return Ok(());
@@ -361,12 +657,165 @@ async fn join(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
     Ok(())
 }
 
+#[group("settings")]
+#[prefixes("settings")]
+#[commands(committees, addcommittee, removecommittee, setdelegaterole)]
+struct Settings;
+
+#[command("committees")]
+#[checks(StaffOrChair)]
+async fn committees(ctx: &Context, msg: &Message) -> CommandResult {
+    let (config, strings) = shared_data(ctx).await;
+    let lang = config.lang();
+
+    if config.committees().is_empty() {
+        msg.reply(ctx, strings.get(lang, "settings_committees_empty"))
+            .await?;
+
+        return Ok(());
+    }
+
+    let mut builder = MessageBuilder::new();
+
+    for committee in config.committees() {
+        builder
+            .push_bold_safe(committee.name())
+            .push(format!(
+                " — role {}, channel {}",
+                committee.role_id(),
+                committee.channel_id()
+            ))
+            .push_line("");
+    }
+
+    msg.channel_id.say(ctx, builder.build()).await?;
+
+    Ok(())
+}
+
+#[command("addcommittee")]
+#[checks(StaffOrChair)]
+async fn addcommittee(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (config_lock, strings) = config_lock_and_strings(ctx).await;
+    let lang = config_lock.read().await.lang().to_owned();
+
+    let role_id = args.single::<u64>();
+    let channel_id = args.single::<u64>();
+
+    let (role_id, channel_id) = match (role_id, channel_id) {
+        (Ok(role_id), Ok(channel_id)) => (role_id, channel_id),
+        _ => {
+            msg.reply(ctx, strings.get(&lang, "settings_invalid_id"))
+                .await?;
+
+            return Ok(());
+        }
+    };
+
+    // The name comes last and may contain spaces (committee names routinely
+    // do, e.g. "Security Council"), so it takes the rest of the args.
+    let name = args.rest().to_owned();
+
+    {
+        let mut config = config_lock.write().await;
+        config.add_committee(Committee::new(name, role_id, channel_id));
+
+        if config.save(CONFIG_PATH).is_err() {
+            msg.reply(ctx, strings.get(&lang, "settings_save_failed"))
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    msg.reply(ctx, strings.get(&lang, "settings_committee_added"))
+        .await?;
+
+    Ok(())
+}
+
+#[command("removecommittee")]
+#[checks(StaffOrChair)]
+async fn removecommittee(ctx: &Context, msg: &Message, args: Args) -> CommandResult {
+    let (config_lock, strings) = config_lock_and_strings(ctx).await;
+    let lang = config_lock.read().await.lang().to_owned();
+
+    let name = args.rest();
+
+    let removed = {
+        let mut config = config_lock.write().await;
+        let removed = config.remove_committee(name);
+
+        if removed && config.save(CONFIG_PATH).is_err() {
+            msg.reply(ctx, strings.get(&lang, "settings_save_failed"))
+                .await?;
+
+            return Ok(());
+        }
+
+        removed
+    };
+
+    let response_key = if removed {
+        "settings_committee_removed"
+    } else {
+        "settings_committee_not_found"
+    };
+
+    msg.reply(ctx, strings.get(&lang, response_key)).await?;
+
+    Ok(())
+}
+
+#[command("setdelegaterole")]
+#[checks(StaffOrChair)]
+async fn setdelegaterole(ctx: &Context, msg: &Message, mut args: Args) -> CommandResult {
+    let (config_lock, strings) = config_lock_and_strings(ctx).await;
+    let lang = config_lock.read().await.lang().to_owned();
+
+    let role_id = match args.single::<u64>() {
+        Ok(role_id) => role_id,
+        Err(_) => {
+            msg.reply(ctx, strings.get(&lang, "settings_invalid_id"))
+                .await?;
+
+            return Ok(());
+        }
+    };
+
+    {
+        let mut config = config_lock.write().await;
+        config.set_delegate_role_id(role_id);
+
+        if config.save(CONFIG_PATH).is_err() {
+            msg.reply(ctx, strings.get(&lang, "settings_save_failed"))
+                .await?;
+
+            return Ok(());
+        }
+    }
+
+    msg.reply(ctx, strings.get(&lang, "settings_delegate_role_updated"))
+        .await?;
+
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     tracing_subscriber::fmt::init();
 
-    let config_file = File::open("config.ron").context("missing config file")?;
-    let config: Config = ron::de::from_reader(config_file).context("invalid config file")?;
+    let config = Config::load(CONFIG_PATH)?;
+
+    let strings = Strings::load(STRINGS_PATH).context("failed to load strings table")?;
+
+    let store = Store::connect(RELAY_DB_PATH)
+        .await
+        .context("failed to open relay store")?;
+
+    // No per-relay listeners to re-register here: `Handler::message` looks up
+    // the relay for each incoming reply via `find_by_committee_msg` on demand,
+    // so every stored relay is already "live" as soon as the client connects.
 
     let bot_id = Http::new_with_token(config.token())
         .get_current_application_info()
@@ -380,7 +829,22 @@ async fn main() -> Result<()> {
                 .on_mention(Some(bot_id))
         })
         .group(&RELAY_GROUP)
-        .group(&ROLE_GROUP);
+        .group(&ROLE_GROUP)
+        .group(&SETTINGS_GROUP)
+        .on_dispatch_error(|ctx, msg, error, _cmd_name| {
+            Box::pin(async move {
+                // Checks report their failure as a strings-table key via
+                // `Reason::User`, so every gate's message stays translatable
+                // without each check needing its own reply logic.
+                if let DispatchError::CheckFailed(_, Reason::User(key)) = error {
+                    let (config, strings) = shared_data(&ctx).await;
+
+                    if let Err(why) = msg.reply(&ctx, strings.get(config.lang(), &key)).await {
+                        warn!("failed to send check-failure reply: {}", why);
+                    }
+                }
+            })
+        });
 
     let mut client = Client::builder(config.token())
         .event_handler(Handler)
@@ -390,7 +854,10 @@ async fn main() -> Result<()> {
 
     {
         let mut data = client.data.write().await;
-        data.insert::<ConfigContainer>(Arc::new(config));
+        data.insert::<ConfigContainer>(Arc::new(RwLock::new(config)));
+        data.insert::<StoreContainer>(Arc::new(store));
+        data.insert::<StringsContainer>(Arc::new(strings));
+        data.insert::<ResolvedCallerContainer>(Arc::new(RwLock::new(HashMap::new())));
     }
 
     client.start().await.context("failed to start client")?;
@@ -0,0 +1,207 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context as _, Result};
+use sqlx::{sqlite::SqlitePoolOptions, sqlite::SqliteRow, Row, SqlitePool};
+
+/// A single delegate <-> committee relay, persisted so it survives restarts
+/// and outlives the 10-minute reply window the bot originally enforced.
+#[derive(Debug, Clone)]
+pub struct Relay {
+    pub id: i64,
+    pub committee_msg_id: u64,
+    pub delegate_channel_id: u64,
+    pub delegate_user_id: u64,
+    pub recipient_id: Option<u64>,
+    /// The committee's channel id; committees have no other stable identifier.
+    pub committee_id: u64,
+    /// `None` for requests that never required committee approval, and for
+    /// external requests whose vote hasn't concluded yet.
+    pub approved: Option<bool>,
+    /// The id of the staff/chair member who cast the deciding vote, if any.
+    pub approved_by: Option<u64>,
+    pub created_at: i64,
+}
+
+/// Fields needed to open a new relay. `id` and `created_at` are assigned by the store.
+#[derive(Debug, Clone)]
+pub struct NewRelay {
+    pub committee_msg_id: u64,
+    pub delegate_channel_id: u64,
+    pub delegate_user_id: u64,
+    pub recipient_id: Option<u64>,
+    pub committee_id: u64,
+    pub approved: Option<bool>,
+}
+
+/// A single logged message in a relay's history, as shown by the `history` command.
+#[derive(Debug, Clone)]
+pub struct RelayMessage {
+    pub author_id: u64,
+    pub content: String,
+    pub created_at: i64,
+}
+
+pub struct Store {
+    pool: SqlitePool,
+}
+
+impl Store {
+    pub async fn connect(path: &str) -> Result<Self> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(&format!("sqlite://{}?mode=rwc", path))
+            .await
+            .context("failed to open relay store")?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS relays (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                committee_msg_id INTEGER NOT NULL UNIQUE,
+                delegate_channel_id INTEGER NOT NULL,
+                delegate_user_id INTEGER NOT NULL,
+                recipient_id INTEGER,
+                committee_id INTEGER NOT NULL,
+                approved INTEGER,
+                approved_by INTEGER,
+                created_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await
+        .context("failed to create relays table")?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS relay_messages (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                relay_id INTEGER NOT NULL REFERENCES relays(id),
+                author_id INTEGER NOT NULL,
+                content TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+        )
+        .execute(&pool)
+        .await
+        .context("failed to create relay_messages table")?;
+
+        Ok(Self { pool })
+    }
+
+    pub async fn insert_relay(&self, relay: &NewRelay) -> Result<i64> {
+        let result = sqlx::query(
+            "INSERT INTO relays
+                (committee_msg_id, delegate_channel_id, delegate_user_id, recipient_id, committee_id, approved, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(relay.committee_msg_id as i64)
+        .bind(relay.delegate_channel_id as i64)
+        .bind(relay.delegate_user_id as i64)
+        .bind(relay.recipient_id.map(|id| id as i64))
+        .bind(relay.committee_id as i64)
+        .bind(relay.approved)
+        .bind(now())
+        .execute(&self.pool)
+        .await
+        .context("failed to insert relay")?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Records a vote's outcome, along with the id of the member who cast it
+    /// (`None` if the vote timed out with nobody responding), so the store
+    /// still attributes a decision after the original Discord reply is gone.
+    /// The `history` command shows only the message log, not this field.
+    pub async fn set_approved(&self, id: i64, approved: bool, approved_by: Option<u64>) -> Result<()> {
+        sqlx::query("UPDATE relays SET approved = ?, approved_by = ? WHERE id = ?")
+            .bind(approved)
+            .bind(approved_by.map(|id| id as i64))
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .context("failed to update relay approval")?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_committee_msg(&self, committee_msg_id: u64) -> Result<Option<Relay>> {
+        let row = sqlx::query("SELECT * FROM relays WHERE committee_msg_id = ?")
+            .bind(committee_msg_id as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .context("failed to look up relay")?;
+
+        row.as_ref().map(row_to_relay).transpose()
+    }
+
+    /// Most recently opened relay for a given delegate channel, for the `history` command.
+    pub async fn latest_for_delegate_channel(&self, delegate_channel_id: u64) -> Result<Option<Relay>> {
+        let row = sqlx::query(
+            "SELECT * FROM relays WHERE delegate_channel_id = ? ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(delegate_channel_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up relay")?;
+
+        row.as_ref().map(row_to_relay).transpose()
+    }
+
+    pub async fn record_message(&self, relay_id: i64, author_id: u64, content: &str) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO relay_messages (relay_id, author_id, content, created_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(relay_id)
+        .bind(author_id as i64)
+        .bind(content)
+        .bind(now())
+        .execute(&self.pool)
+        .await
+        .context("failed to record relay message")?;
+
+        Ok(())
+    }
+
+    pub async fn history(&self, relay_id: i64) -> Result<Vec<RelayMessage>> {
+        let rows = sqlx::query(
+            "SELECT author_id, content, created_at FROM relay_messages
+             WHERE relay_id = ? ORDER BY created_at ASC",
+        )
+        .bind(relay_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to load relay history")?;
+
+        Ok(rows
+            .iter()
+            .map(|row| RelayMessage {
+                author_id: row.get::<i64, _>("author_id") as u64,
+                content: row.get("content"),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+}
+
+fn row_to_relay(row: &SqliteRow) -> Result<Relay> {
+    Ok(Relay {
+        id: row.get("id"),
+        committee_msg_id: row.get::<i64, _>("committee_msg_id") as u64,
+        delegate_channel_id: row.get::<i64, _>("delegate_channel_id") as u64,
+        delegate_user_id: row.get::<i64, _>("delegate_user_id") as u64,
+        recipient_id: row
+            .get::<Option<i64>, _>("recipient_id")
+            .map(|id| id as u64),
+        committee_id: row.get::<i64, _>("committee_id") as u64,
+        approved: row.get("approved"),
+        approved_by: row
+            .get::<Option<i64>, _>("approved_by")
+            .map(|id| id as u64),
+        created_at: row.get("created_at"),
+    })
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
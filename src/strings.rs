@@ -0,0 +1,54 @@
+use std::{collections::HashMap, fs::File};
+
+use anyhow::{Context as _, Result};
+use serde::Deserialize;
+
+pub const DEFAULT_LANG: &str = "en";
+
+/// A keyed table of user-facing strings for a single language, loaded from
+/// `strings.ron`. Values may contain `{placeholder}` markers that callers
+/// fill in with [`Strings::get_fmt`].
+#[derive(Debug, Deserialize)]
+struct Table(HashMap<String, String>);
+
+/// All loaded language tables, keyed by language code (e.g. `"en"`, `"fr"`).
+#[derive(Debug, Deserialize)]
+pub struct Strings {
+    #[serde(flatten)]
+    tables: HashMap<String, Table>,
+}
+
+impl Strings {
+    pub fn load(path: &str) -> Result<Self> {
+        let file = File::open(path).context("missing strings file")?;
+
+        ron::de::from_reader(file).context("invalid strings file")
+    }
+
+    /// Looks up `key` in `lang`, falling back to [`DEFAULT_LANG`], then to the
+    /// key itself so a missing translation never panics the bot.
+    pub fn get<'a>(&'a self, lang: &str, key: &str) -> &'a str {
+        self.tables
+            .get(lang)
+            .and_then(|table| table.0.get(key))
+            .or_else(|| {
+                self.tables
+                    .get(DEFAULT_LANG)
+                    .and_then(|table| table.0.get(key))
+            })
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Like [`Strings::get`], substituting each `{name}` placeholder with its
+    /// value from `vars`.
+    pub fn get_fmt(&self, lang: &str, key: &str, vars: &[(&str, &str)]) -> String {
+        let mut value = self.get(lang, key).to_string();
+
+        for (name, replacement) in vars {
+            value = value.replace(&format!("{{{}}}", name), replacement);
+        }
+
+        value
+    }
+}
@@ -1,58 +1,246 @@
-use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Write as _};
+
+use anyhow::{Context as _, Result};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct Config {
     token: String,
-    guild_id: String,
-    delegate_role_id: String,
-    staff_role_id: String,
-    chair_role_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    guild_id: u64,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    delegate_role_id: u64,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    staff_role_id: u64,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    chair_role_id: u64,
     committees: Vec<Committee>,
+    #[serde(default)]
+    lang: Option<String>,
+    #[serde(default = "default_true")]
+    webhooks_enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Parses an id stored as a quoted string (to dodge precision loss in any
+/// non-Rust tooling that touches `config.ron`) into the `u64` Discord
+/// actually uses, failing load with context instead of panicking later.
+fn deserialize_id<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+
+    raw.parse()
+        .map_err(|_| D::Error::custom(format!("not a valid id: {:?}", raw)))
+}
+
+fn serialize_id<S>(id: &u64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&id.to_string())
+}
+
+fn deserialize_opt_id<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw: Option<String> = Option::deserialize(deserializer)?;
+
+    raw.map(|raw| {
+        raw.parse()
+            .map_err(|_| D::Error::custom(format!("not a valid id: {:?}", raw)))
+    })
+    .transpose()
+}
+
+fn serialize_opt_id<S>(id: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match id {
+        Some(id) => serializer.serialize_some(&id.to_string()),
+        None => serializer.serialize_none(),
+    }
 }
 
 impl Config {
+    /// Loads and validates `config.ron`. Malformed ids are rejected here,
+    /// up front, rather than panicking the first time an accessor is called.
+    pub fn load(path: &str) -> Result<Self> {
+        let file = File::open(path).context("missing config file")?;
+
+        ron::de::from_reader(file).context("invalid config file")
+    }
+
+    /// Persists this config back to `path`, replacing it atomically so a
+    /// crash mid-write can never leave behind a truncated file.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let serialized = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .context("failed to serialize config")?;
+
+        let tmp_path = format!("{}.tmp", path);
+
+        File::create(&tmp_path)
+            .context("failed to create temporary config file")?
+            .write_all(serialized.as_bytes())
+            .context("failed to write temporary config file")?;
+
+        std::fs::rename(&tmp_path, path).context("failed to replace config file")?;
+
+        Ok(())
+    }
+
     pub fn token(&self) -> &str {
         &self.token
     }
 
+    /// The language code to look up strings under, e.g. `"en"` or `"fr"`.
+    /// Falls back to [`crate::strings::DEFAULT_LANG`] when unset.
+    pub fn lang(&self) -> &str {
+        self.lang.as_deref().unwrap_or(crate::strings::DEFAULT_LANG)
+    }
+
     pub fn guild_id(&self) -> u64 {
-        self.guild_id.parse().unwrap()
+        self.guild_id
     }
 
     pub fn delegate_role_id(&self) -> u64 {
-        self.delegate_role_id.parse().unwrap()
+        self.delegate_role_id
+    }
+
+    pub fn set_delegate_role_id(&mut self, role_id: u64) {
+        self.delegate_role_id = role_id;
     }
 
     pub fn staff_role_id(&self) -> u64 {
-        self.staff_role_id.parse().unwrap()
+        self.staff_role_id
     }
 
     pub fn chair_role_id(&self) -> u64 {
-        self.chair_role_id.parse().unwrap()
+        self.chair_role_id
     }
 
     pub fn committees(&self) -> &[Committee] {
         &self.committees
     }
+
+    pub fn add_committee(&mut self, committee: Committee) {
+        self.committees.push(committee);
+    }
+
+    /// Looks up a committee by its channel.
+    pub fn committee(&self, channel_id: u64) -> Option<&Committee> {
+        self.committees
+            .iter()
+            .find(|committee| committee.channel_id() == channel_id)
+    }
+
+    /// Looks up a committee by its channel, for caching its relay webhook
+    /// credentials once created.
+    pub fn committee_mut(&mut self, channel_id: u64) -> Option<&mut Committee> {
+        self.committees
+            .iter_mut()
+            .find(|committee| committee.channel_id() == channel_id)
+    }
+
+    /// Removes the committee named `name`, returning whether one was found.
+    pub fn remove_committee(&mut self, name: &str) -> bool {
+        let len_before = self.committees.len();
+
+        self.committees
+            .retain(|committee| !committee.name.eq_ignore_ascii_case(name));
+
+        self.committees.len() != len_before
+    }
+
+    /// Whether committee relay messages should be delivered through a
+    /// per-committee webhook impersonating the delegate, rather than
+    /// authored by the bot.
+    pub fn webhooks_enabled(&self) -> bool {
+        self.webhooks_enabled
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Committee {
     name: String,
-    role_id: String,
-    channel_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    role_id: u64,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    channel_id: u64,
+    #[serde(default)]
+    webhook_name: Option<String>,
+    #[serde(default)]
+    webhook_avatar: Option<String>,
+    /// The relay webhook registered for this committee's channel, cached
+    /// here after creation so `forward` doesn't have to re-list (and guess
+    /// at) the channel's webhooks on every call.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_opt_id",
+        serialize_with = "serialize_opt_id"
+    )]
+    webhook_id: Option<u64>,
+    #[serde(default)]
+    webhook_token: Option<String>,
 }
 
 impl Committee {
+    pub fn new(name: String, role_id: u64, channel_id: u64) -> Self {
+        Self {
+            name,
+            role_id,
+            channel_id,
+            webhook_name: None,
+            webhook_avatar: None,
+            webhook_id: None,
+            webhook_token: None,
+        }
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
 
     pub fn role_id(&self) -> u64 {
-        self.role_id.parse().unwrap()
+        self.role_id
     }
 
     pub fn channel_id(&self) -> u64 {
-        self.channel_id.parse().unwrap()
+        self.channel_id
+    }
+
+    /// Overrides the registered name of this committee's relay webhook.
+    /// Defaults to `"Mun Relay"` when unset.
+    pub fn webhook_name(&self) -> Option<&str> {
+        self.webhook_name.as_deref()
+    }
+
+    /// Fallback avatar URL applied at message-send time when the delegate
+    /// posting through this committee's relay webhook has none of their own.
+    /// Not used at webhook-creation time: Discord's create-webhook endpoint
+    /// wants base64 image data for its `avatar` field, not a URL, and this is
+    /// simpler to apply per-message the same way the delegate's avatar is.
+    pub fn webhook_avatar(&self) -> Option<&str> {
+        self.webhook_avatar.as_deref()
+    }
+
+    /// The cached id/token of this committee's relay webhook, if one has
+    /// already been created.
+    pub fn webhook_credentials(&self) -> Option<(u64, &str)> {
+        match (self.webhook_id, self.webhook_token.as_deref()) {
+            (Some(id), Some(token)) => Some((id, token)),
+            _ => None,
+        }
+    }
+
+    pub fn set_webhook_credentials(&mut self, id: u64, token: String) {
+        self.webhook_id = Some(id);
+        self.webhook_token = Some(token);
     }
 }